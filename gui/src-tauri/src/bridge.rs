@@ -0,0 +1,152 @@
+//! The MCP bridge: owns the child process that speaks the Model Context Protocol over a
+//! single stdio, line-delimited JSON channel and exposes it as typed async calls.
+//!
+//! Every call here goes over that one channel and holds its lock for the whole
+//! request/response exchange, so calls reach the child one at a time whatever lock callers
+//! (see [`crate::commands::task::execute_intent`]) take on the `Bridge` itself.
+
+use std::fmt;
+use std::process::Stdio;
+
+use serde_json::{json, Value};
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::process::{Child, ChildStdin, ChildStdout, Command};
+use tokio::sync::Mutex;
+
+#[derive(Debug)]
+pub struct BridgeError(String);
+
+impl fmt::Display for BridgeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl std::error::Error for BridgeError {}
+
+impl From<std::io::Error> for BridgeError {
+    fn from(e: std::io::Error) -> Self {
+        BridgeError(e.to_string())
+    }
+}
+
+struct Channel {
+    stdin: ChildStdin,
+    stdout: BufReader<ChildStdout>,
+    next_id: u64,
+}
+
+/// Owns the MCP server child process and the single stdio channel used to invoke its
+/// tools, persist frontend state, and report connection health.
+pub struct Bridge {
+    command: String,
+    storage_mode: String,
+    child: Option<Child>,
+    channel: Mutex<Option<Channel>>,
+    last_error: Option<BridgeError>,
+}
+
+impl Bridge {
+    pub fn new(command: impl Into<String>, storage_mode: impl Into<String>) -> Self {
+        Self {
+            command: command.into(),
+            storage_mode: storage_mode.into(),
+            child: None,
+            channel: Mutex::new(None),
+            last_error: None,
+        }
+    }
+
+    pub fn is_connected(&self) -> bool {
+        self.child.is_some()
+    }
+
+    pub fn storage_mode_str(&self) -> &str {
+        &self.storage_mode
+    }
+
+    pub fn last_error(&self) -> Option<&BridgeError> {
+        self.last_error.as_ref()
+    }
+
+    async fn spawn(&mut self) -> Result<(), BridgeError> {
+        let mut child = Command::new(&self.command)
+            .arg("--storage-mode")
+            .arg(&self.storage_mode)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .spawn()?;
+
+        let stdin = child.stdin.take().ok_or_else(|| BridgeError("child has no stdin".to_string()))?;
+        let stdout = child.stdout.take().ok_or_else(|| BridgeError("child has no stdout".to_string()))?;
+
+        self.child = Some(child);
+        *self.channel.lock().await = Some(Channel { stdin, stdout: BufReader::new(stdout), next_id: 0 });
+        Ok(())
+    }
+
+    /// Stop the child process and drop the channel, leaving `self` reconnectable.
+    pub async fn disconnect(&mut self) -> Result<(), BridgeError> {
+        *self.channel.lock().await = None;
+        if let Some(mut child) = self.child.take() {
+            child.kill().await?;
+        }
+        Ok(())
+    }
+
+    /// Disconnect (if connected) and spawn a fresh child with the current storage mode.
+    pub async fn reconnect(&mut self) -> Result<(), BridgeError> {
+        self.disconnect().await?;
+        match self.spawn().await {
+            Ok(()) => {
+                self.last_error = None;
+                Ok(())
+            }
+            Err(e) => {
+                self.last_error = Some(BridgeError(e.to_string()));
+                Err(e)
+            }
+        }
+    }
+
+    /// Switch storage mode, restarting the child against the new mode if it actually
+    /// changed. Returns whether a restart happened.
+    pub async fn set_storage_mode(&mut self, mode: &str) -> Result<bool, BridgeError> {
+        if mode == self.storage_mode {
+            return Ok(false);
+        }
+        self.storage_mode = mode.to_string();
+        self.reconnect().await?;
+        Ok(true)
+    }
+
+    async fn call(&self, method: &str, params: Value) -> Result<Value, BridgeError> {
+        let mut guard = self.channel.lock().await;
+        let channel = guard.as_mut().ok_or_else(|| BridgeError("bridge is not connected".to_string()))?;
+
+        channel.next_id += 1;
+        let request = json!({ "id": channel.next_id, "method": method, "params": params });
+        channel.stdin.write_all(request.to_string().as_bytes()).await?;
+        channel.stdin.write_all(b"\n").await?;
+
+        let mut line = String::new();
+        channel.stdout.read_line(&mut line).await?;
+        serde_json::from_str(&line).map_err(|e| BridgeError(e.to_string()))
+    }
+
+    /// Invoke `tool_name` (e.g. `tasks_list`) with `params`, returning the tool's envelope
+    /// verbatim.
+    pub async fn invoke(&self, tool_name: &str, params: Option<Value>) -> Result<Value, BridgeError> {
+        self.call(tool_name, params.unwrap_or(json!({}))).await
+    }
+
+    pub async fn get_frontend_state(&self, namespace: &str) -> Result<Option<Value>, BridgeError> {
+        let result = self.call("frontend_state_get", json!({ "namespace": namespace })).await?;
+        Ok(result.get("value").cloned())
+    }
+
+    pub async fn set_frontend_state(&self, namespace: &str, value: Value) -> Result<(), BridgeError> {
+        self.call("frontend_state_set", json!({ "namespace": namespace, "value": value })).await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,4 @@
+//! Tauri command modules: the intent surface (`task`) and its schema registry.
+
+pub mod registry;
+pub mod task;
@@ -0,0 +1,216 @@
+//! Typed registry of `tasks_<intent>` parameter schemas.
+//!
+//! `ai_intent` validates against this before ever reaching the bridge, so unknown or
+//! malformed calls fail fast with actionable `suggestions`/`context` instead of whatever
+//! error the MCP tool itself happens to raise.
+
+use serde_json::Value;
+
+/// The shape a single parameter is expected to have.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParamKind {
+    String,
+    Number,
+    Bool,
+    Object,
+    Array,
+}
+
+impl ParamKind {
+    fn matches(self, value: &Value) -> bool {
+        match self {
+            ParamKind::String => value.is_string(),
+            ParamKind::Number => value.is_number(),
+            ParamKind::Bool => value.is_boolean(),
+            ParamKind::Object => value.is_object(),
+            ParamKind::Array => value.is_array(),
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ParamKind::String => "string",
+            ParamKind::Number => "number",
+            ParamKind::Bool => "bool",
+            ParamKind::Object => "object",
+            ParamKind::Array => "array",
+        }
+    }
+}
+
+/// A single declared parameter on an [`IntentSchema`].
+#[derive(Debug, Clone, Copy)]
+pub struct ParamSpec {
+    pub name: &'static str,
+    pub required: bool,
+    pub kind: ParamKind,
+}
+
+/// The declared parameter schema for one registered `tasks_<intent>` name.
+#[derive(Debug, Clone, Copy)]
+pub struct IntentSchema {
+    pub name: &'static str,
+    pub params: &'static [ParamSpec],
+    /// Whether this intent changes backend state. The bridge's single MCP channel isn't
+    /// proven safe for overlapping in-flight calls, so `execute_intent` takes the bridge's
+    /// exclusive writer lock (serializing against every other call) for these, while
+    /// read-only intents share the reader lock. Their bridge requests still reach the MCP
+    /// server one at a time, since the bridge holds its channel lock for each exchange.
+    pub mutates: bool,
+}
+
+/// A schema mismatch: the offending field and a human-readable explanation.
+#[derive(Debug, Clone)]
+pub struct ValidationError {
+    pub field: String,
+    pub message: String,
+}
+
+const LIST_PARAMS: &[ParamSpec] = &[
+    ParamSpec { name: "filter", required: false, kind: ParamKind::Object },
+    ParamSpec { name: "limit", required: false, kind: ParamKind::Number },
+];
+
+const GET_PARAMS: &[ParamSpec] = &[ParamSpec { name: "id", required: true, kind: ParamKind::String }];
+
+const CREATE_PARAMS: &[ParamSpec] = &[
+    ParamSpec { name: "title", required: true, kind: ParamKind::String },
+    ParamSpec { name: "description", required: false, kind: ParamKind::String },
+    ParamSpec { name: "status", required: false, kind: ParamKind::String },
+];
+
+const UPDATE_PARAMS: &[ParamSpec] = &[
+    ParamSpec { name: "id", required: true, kind: ParamKind::String },
+    ParamSpec { name: "fields", required: false, kind: ParamKind::Object },
+];
+
+const DELETE_PARAMS: &[ParamSpec] = &[ParamSpec { name: "id", required: true, kind: ParamKind::String }];
+
+const BULK_DELETE_PARAMS: &[ParamSpec] =
+    &[ParamSpec { name: "ids", required: true, kind: ParamKind::Array }];
+
+const BULK_UPDATE_STATUS_PARAMS: &[ParamSpec] = &[
+    ParamSpec { name: "ids", required: true, kind: ParamKind::Array },
+    ParamSpec { name: "status", required: true, kind: ParamKind::String },
+];
+
+const CLEAR_PARAMS: &[ParamSpec] = &[];
+
+/// Every `tasks_<intent>` this app knows how to call, keyed by its bare intent name
+/// (without the `tasks_` prefix `ai_intent` adds).
+pub const REGISTRY: &[IntentSchema] = &[
+    IntentSchema { name: "list", params: LIST_PARAMS, mutates: false },
+    IntentSchema { name: "get", params: GET_PARAMS, mutates: false },
+    IntentSchema { name: "create", params: CREATE_PARAMS, mutates: true },
+    IntentSchema { name: "update", params: UPDATE_PARAMS, mutates: true },
+    IntentSchema { name: "delete", params: DELETE_PARAMS, mutates: true },
+    IntentSchema { name: "bulk_delete", params: BULK_DELETE_PARAMS, mutates: true },
+    IntentSchema { name: "bulk_update_status", params: BULK_UPDATE_STATUS_PARAMS, mutates: true },
+    IntentSchema { name: "clear", params: CLEAR_PARAMS, mutates: true },
+];
+
+/// Look up a schema by its bare intent name (e.g. `"delete"`, not `"tasks_delete"`).
+pub fn find(intent: &str) -> Option<&'static IntentSchema> {
+    REGISTRY.iter().find(|schema| schema.name == intent)
+}
+
+/// Validate `params` against `schema`, returning the first missing or mistyped field.
+pub fn validate(schema: &IntentSchema, params: &Value) -> Result<(), ValidationError> {
+    for spec in schema.params {
+        match params.get(spec.name) {
+            Some(value) if !spec.kind.matches(value) => {
+                return Err(ValidationError {
+                    field: spec.name.to_string(),
+                    message: format!("expected `{}` to be a {}", spec.name, spec.kind.as_str()),
+                });
+            }
+            None if spec.required => {
+                return Err(ValidationError {
+                    field: spec.name.to_string(),
+                    message: format!("missing required field `{}`", spec.name),
+                });
+            }
+            _ => {}
+        }
+    }
+    Ok(())
+}
+
+/// Classic Wagner-Fischer edit distance between two strings.
+fn levenshtein(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+
+    for i in 1..=a.len() {
+        let mut prev = row[0];
+        row[0] = i;
+        for j in 1..=b.len() {
+            let cost = if a[i - 1] == b[j - 1] { 0 } else { 1 };
+            let current = (row[j] + 1).min(row[j - 1] + 1).min(prev + cost);
+            prev = row[j];
+            row[j] = current;
+        }
+    }
+
+    row[b.len()]
+}
+
+/// Maximum edit distance still considered a plausible typo.
+const SUGGESTION_THRESHOLD: usize = 3;
+
+/// Return up to three registered intent names closest to `intent` by edit distance,
+/// for use as the `suggestions` array on an `UNKNOWN_INTENT` error.
+pub fn suggestions(intent: &str) -> Vec<String> {
+    let mut scored: Vec<(usize, &'static str)> = REGISTRY
+        .iter()
+        .map(|schema| (levenshtein(intent, schema.name), schema.name))
+        .filter(|(distance, _)| *distance <= SUGGESTION_THRESHOLD)
+        .collect();
+
+    scored.sort_by_key(|(distance, name)| (*distance, *name));
+    scored.into_iter().take(3).map(|(_, name)| name.to_string()).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn suggestions_catches_a_plausible_typo() {
+        // "list" is one edit away; "get" is two substitutions away, still within the threshold.
+        assert_eq!(suggestions("lst"), vec!["list".to_string(), "get".to_string()]);
+    }
+
+    #[test]
+    fn suggestions_ignores_wildly_different_input() {
+        assert!(suggestions("xyzzy").is_empty());
+    }
+
+    #[test]
+    fn validate_rejects_a_missing_required_field() {
+        let schema = find("get").unwrap();
+        let err = validate(schema, &json!({})).unwrap_err();
+        assert_eq!(err.field, "id");
+    }
+
+    #[test]
+    fn validate_rejects_a_type_mismatch() {
+        let schema = find("get").unwrap();
+        let err = validate(schema, &json!({ "id": 5 })).unwrap_err();
+        assert_eq!(err.field, "id");
+    }
+
+    #[test]
+    fn validate_accepts_a_well_formed_call() {
+        let schema = find("get").unwrap();
+        assert!(validate(schema, &json!({ "id": "task-1" })).is_ok());
+    }
+
+    #[test]
+    fn validate_allows_omitting_optional_fields() {
+        let schema = find("list").unwrap();
+        assert!(validate(schema, &json!({})).is_ok());
+    }
+}
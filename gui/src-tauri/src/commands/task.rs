@@ -2,11 +2,26 @@
 //!
 //! These commands are invoked from the React frontend via Tauri's invoke API.
 
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+
 use serde_json::{json, Value};
-use tauri::State;
+use tauri::{async_runtime, AppHandle, Emitter, State};
+use tokio::sync::{Mutex, RwLock};
 
+use crate::bridge::Bridge;
 use crate::AppState;
 
+use super::registry;
+
+/// Intent names that mutate or drop data destructively enough to require explicit
+/// frontend confirmation before `ai_intent` forwards them to the bridge.
+const GUARDED_INTENTS: &[&str] = &["delete", "bulk_delete", "bulk_update_status", "clear"];
+
+pub(crate) fn is_guarded_intent(normalized_intent: &str) -> bool {
+    GUARDED_INTENTS.contains(&normalized_intent)
+}
+
 /// Backend storage mode response
 #[derive(Debug, serde::Serialize, serde::Deserialize)]
 pub struct BackendStorageModeResponse {
@@ -14,9 +29,12 @@ pub struct BackendStorageModeResponse {
     pub mode: String,
     pub restarted: bool,
     pub error: Option<String>,
+    /// Set when the switch was deferred for frontend confirmation instead of applied
+    /// immediately; resolve it with [`respond_intent`].
+    pub pending_approval_id: Option<u64>,
 }
 
-fn bridge_error(intent: &str, message: String) -> Value {
+pub(crate) fn bridge_error(intent: &str, message: String) -> Value {
     json!({
         "success": false,
         "intent": intent,
@@ -30,45 +48,734 @@ fn bridge_error(intent: &str, message: String) -> Value {
     })
 }
 
+pub(crate) fn unknown_intent_error(intent: &str, suggestions: Vec<String>) -> Value {
+    json!({
+        "success": false,
+        "intent": intent,
+        "result": {},
+        "warnings": [],
+        "context": {},
+        "suggestions": suggestions,
+        "meta": {},
+        "error": { "code": "UNKNOWN_INTENT", "message": format!("no such intent `{intent}`") },
+        "timestamp": ""
+    })
+}
+
+pub(crate) fn invalid_params_error(intent: &str, field: &str, message: &str) -> Value {
+    json!({
+        "success": false,
+        "intent": intent,
+        "result": {},
+        "warnings": [],
+        "context": { "field": field },
+        "suggestions": [],
+        "meta": {},
+        "error": { "code": "INVALID_PARAMS", "message": message },
+        "timestamp": ""
+    })
+}
+
+pub(crate) fn bridge_disconnected_error(intent: &str) -> Value {
+    json!({
+        "success": false,
+        "intent": intent,
+        "result": {},
+        "warnings": [],
+        "context": {},
+        "suggestions": [],
+        "meta": {},
+        "error": {
+            "code": "BRIDGE_DISCONNECTED",
+            "message": "the MCP bridge is not connected; call backend_reconnect to restore it",
+        },
+        "timestamp": ""
+    })
+}
+
+/// A guarded intent was reached from a path that can't show the frontend's approval dialog
+/// (the HTTP surface, or a batch item — see [`execute_intent`]'s `allow_guarded_approval`).
+pub(crate) fn guarded_intent_forbidden(intent: &str) -> Value {
+    json!({
+        "success": false,
+        "intent": intent,
+        "result": {},
+        "warnings": [],
+        "context": {},
+        "suggestions": [],
+        "meta": {},
+        "error": {
+            "code": "GUARDED_INTENT_REQUIRES_UI",
+            "message": "this intent is destructive and can only be confirmed through the desktop app's approval dialog; it is refused on this path",
+        },
+        "timestamp": ""
+    })
+}
+
+/// What a stashed approval does once the frontend resolves it.
+#[derive(Debug, Clone)]
+pub enum PendingAction {
+    /// Forward `params` to `tool_name` over the bridge, exactly as [`execute_intent`] would
+    /// have done had the intent not been guarded.
+    Intent { tool_name: String, params: Value },
+    /// Apply a storage-mode switch, restarting the bridge's backend process if the mode
+    /// actually changes — see [`backend_set_storage_mode`].
+    StorageMode { mode: String },
+}
+
+/// A destructive call stashed until the frontend approves or denies it.
+#[derive(Debug, Clone)]
+pub struct PendingApproval {
+    /// The bare intent name (e.g. `"delete"`, `"set_storage_mode"`), not the
+    /// `tasks_`-prefixed tool name — kept separate so a resolved approval reports errors the
+    /// same way every other path does.
+    pub intent: String,
+    pub action: PendingAction,
+}
+
+/// Payload of the `approval-requested` event emitted when `ai_intent` defers a guarded intent
+/// or `backend_set_storage_mode` defers a storage-mode switch.
+#[derive(Debug, serde::Serialize)]
+struct ApprovalRequestedPayload {
+    id: u64,
+    tool_name: String,
+    summary: String,
+}
+
+fn approval_required(intent: &str, id: u64) -> Value {
+    json!({
+        "success": false,
+        "intent": intent,
+        "result": {},
+        "warnings": [],
+        "context": {},
+        "suggestions": [],
+        "meta": {},
+        "error": {
+            "code": "APPROVAL_REQUIRED",
+            "message": "this intent requires frontend confirmation before it runs",
+            "id": id,
+        },
+        "timestamp": ""
+    })
+}
+
+/// Check connectivity and forward `params` to `tool_name` over an already-acquired bridge
+/// handle (read- or write-locked, see [`execute_intent`]). Shared so both lock branches wrap
+/// the invocation identically, and reused as-is by the HTTP surface in [`crate::http_server`].
+pub(crate) async fn invoke_checked(bridge: &Bridge, intent: &str, tool_name: &str, params: Value) -> Value {
+    if !bridge.is_connected() {
+        return bridge_disconnected_error(intent);
+    }
+
+    match bridge.invoke(tool_name, Some(params)).await {
+        Ok(result) => result,
+        Err(e) => bridge_error(intent, e.to_string()),
+    }
+}
+
+/// Validate and run a single intent call: registry lookup, schema validation, the
+/// destructive-intent approval gate, the dead-bridge check, and finally the bridge call
+/// itself. Shared by [`ai_intent`] and [`ai_intent_batch`] so a batch item can't reach the
+/// bridge by a path that skips any of those guards.
+///
+/// `allow_guarded_approval` controls what happens when the intent is in [`GUARDED_INTENTS`]:
+/// `ai_intent` passes `true` and gets the usual stash-and-emit approval handshake, while
+/// `ai_intent_batch` always passes `false` — deferring a batch item to an out-of-band
+/// approval would silently reorder or break the batch's length/atomicity contract, so guarded
+/// intents are refused outright inside a batch instead (see [`guarded_intent_forbidden`]),
+/// the same way the HTTP surface refuses them.
+///
+/// The bridge's single MCP channel isn't proven safe for overlapping in-flight calls, so
+/// mutating intents (`schema.mutates`, e.g. `create`/`delete`) take the bridge's exclusive
+/// *write* lock, serializing against every other call; read-only intents (`list`/`get`) only
+/// take the *read* side and don't wait on each other for it. Their requests still reach the
+/// MCP server one at a time, since the bridge holds its channel lock for each exchange.
+async fn execute_intent(
+    app: &AppHandle,
+    bridge: &Arc<RwLock<Bridge>>,
+    pending_approvals: &Arc<Mutex<std::collections::HashMap<u64, PendingApproval>>>,
+    next_approval_id: &Arc<AtomicU64>,
+    intent: String,
+    params: Option<Value>,
+    allow_guarded_approval: bool,
+) -> Value {
+    let normalized_intent = intent.trim().to_lowercase();
+    let tool_name = format!("tasks_{}", normalized_intent);
+    let request_params = params.unwrap_or(json!({}));
+
+    let Some(schema) = registry::find(&normalized_intent) else {
+        return unknown_intent_error(&normalized_intent, registry::suggestions(&normalized_intent));
+    };
+
+    if let Err(err) = registry::validate(schema, &request_params) {
+        return invalid_params_error(&normalized_intent, &err.field, &err.message);
+    }
+
+    if is_guarded_intent(&normalized_intent) {
+        if !allow_guarded_approval {
+            return guarded_intent_forbidden(&normalized_intent);
+        }
+
+        let id = next_approval_id.fetch_add(1, Ordering::SeqCst);
+
+        {
+            let mut pending = pending_approvals.lock().await;
+            pending.insert(
+                id,
+                PendingApproval {
+                    intent: normalized_intent.clone(),
+                    action: PendingAction::Intent { tool_name: tool_name.clone(), params: request_params },
+                },
+            );
+        }
+
+        let _ = app.emit(
+            "approval-requested",
+            ApprovalRequestedPayload {
+                id,
+                tool_name: tool_name.clone(),
+                summary: format!("Run `{tool_name}`? This action cannot be undone."),
+            },
+        );
+
+        return approval_required(&normalized_intent, id);
+    }
+
+    if schema.mutates {
+        let bridge = bridge.write().await;
+        invoke_checked(&bridge, &normalized_intent, &tool_name, request_params).await
+    } else {
+        let bridge = bridge.read().await;
+        invoke_checked(&bridge, &normalized_intent, &tool_name, request_params).await
+    }
+}
+
 /// Execute AI intent (transparent proxy to MCP tools: tasks_<intent>)
+///
+/// Intents in [`GUARDED_INTENTS`] are not forwarded to the bridge immediately: the call is
+/// stashed in [`AppState::pending_approvals`] under a freshly allocated id, an
+/// `approval-requested` event is emitted so the frontend can prompt the user, and the
+/// envelope returned here carries `error.code = "APPROVAL_REQUIRED"` plus that id. The
+/// frontend resolves it by calling [`respond_intent`].
 #[tauri::command]
 pub async fn ai_intent(
+    app: AppHandle,
     state: State<'_, AppState>,
     intent: String,
     params: Option<Value>,
 ) -> Result<Value, String> {
-    let bridge = state.bridge.lock().await;
+    Ok(execute_intent(
+        &app,
+        &state.bridge,
+        &state.pending_approvals,
+        &state.next_approval_id,
+        intent,
+        params,
+        true,
+    )
+    .await)
+}
 
-    let normalized_intent = intent.trim().to_lowercase();
-    let tool_name = format!("tasks_{}", normalized_intent);
+/// Approve or deny a guarded intent call previously stashed by [`ai_intent`].
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Approval {
+    Approved,
+    Denied,
+}
 
-    let request_params = params.unwrap_or(json!({}));
+/// Resolve a pending guarded-intent call: forward it to the bridge if approved, or drop it
+/// and report that it was denied.
+#[tauri::command]
+pub async fn respond_intent(
+    state: State<'_, AppState>,
+    id: u64,
+    approval: Approval,
+) -> Result<Value, String> {
+    let pending = {
+        let mut pending = state.pending_approvals.lock().await;
+        pending.remove(&id)
+    };
+
+    let Some(pending) = pending else {
+        return Ok(bridge_error("unknown", format!("no pending approval with id {id}")));
+    };
+
+    match approval {
+        Approval::Denied => Ok(json!({
+            "success": false,
+            "intent": pending.intent,
+            "result": {},
+            "warnings": [],
+            "context": {},
+            "suggestions": [],
+            "meta": {},
+            "error": { "code": "APPROVAL_DENIED", "message": "the user denied this action" },
+            "timestamp": ""
+        })),
+        // Every pending approval is destructive (hence mutating), so both arms below take
+        // the bridge's exclusive writer lock, same as `execute_intent` would for an intent
+        // call had it not been deferred for approval.
+        Approval::Approved => match pending.action {
+            PendingAction::Intent { tool_name, params } => {
+                let bridge = state.bridge.write().await;
+                Ok(invoke_checked(&bridge, &pending.intent, &tool_name, params).await)
+            }
+            PendingAction::StorageMode { mode } => {
+                let mut bridge = state.bridge.write().await;
+                match bridge.set_storage_mode(&mode).await {
+                    Ok(restarted) => Ok(json!({
+                        "success": true,
+                        "intent": pending.intent,
+                        "result": { "mode": bridge.storage_mode_str(), "restarted": restarted },
+                        "warnings": [],
+                        "context": {},
+                        "suggestions": [],
+                        "meta": {},
+                        "error": null,
+                        "timestamp": ""
+                    })),
+                    Err(e) => Ok(bridge_error(&pending.intent, e.to_string())),
+                }
+            }
+        },
+    }
+}
 
-    match bridge.invoke(&tool_name, Some(request_params)).await {
-        Ok(result) => Ok(result),
-        Err(e) => Ok(bridge_error(&normalized_intent, e.to_string())),
+/// A single entry in an `ai_intent_batch` request
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+pub struct BatchIntentRequest {
+    pub intent: String,
+    pub params: Option<Value>,
+}
+
+/// Execute a batch of AI intents, preserving input ordering in the result, through the same
+/// registry/approval/connectivity guards `ai_intent` itself uses. Guarded intents (see
+/// [`GUARDED_INTENTS`]) are always refused with [`guarded_intent_forbidden`] rather than
+/// stashed for out-of-band approval: deferring one mid-batch would silently reorder or break
+/// the batch's length/atomicity contract, so a destructive intent must be run individually
+/// through `ai_intent`/`respond_intent` instead.
+///
+/// `stop_on_error` picks between two genuinely different execution strategies, not just a
+/// different read of the same run:
+///
+/// - `false`: every item is fanned out concurrently via [`execute_intent`] on Tauri's async
+///   runtime. Read-only items share the bridge's reader lock (see `execute_intent`'s
+///   `mutates` branch), though their requests still go over the bridge's single channel one
+///   at a time; mutating items serialize behind the bridge's writer lock but are
+///   still dispatched up front. There's no ordering guarantee between items' side effects.
+/// - `true`: items run strictly one at a time, in order, and execution stops for good after
+///   the first `BRIDGE_ERROR`- or `BRIDGE_DISCONNECTED`-coded envelope (see
+///   [`is_fatal_bridge_error`]) — so a "skipped" result is never a lie: a
+///   skipped item's `execute_intent` call genuinely never happened. This trades away
+///   concurrency in exchange for that guarantee, which only matters when the caller cares
+///   about abandoning the rest of the batch on failure in the first place.
+#[tauri::command]
+pub async fn ai_intent_batch(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    items: Vec<BatchIntentRequest>,
+    stop_on_error: bool,
+) -> Result<Vec<Value>, String> {
+    let bridge = state.bridge.clone();
+    let pending_approvals = state.pending_approvals.clone();
+    let next_approval_id = state.next_approval_id.clone();
+
+    if stop_on_error {
+        Ok(run_batch_sequential(&app, &bridge, &pending_approvals, &next_approval_id, items).await)
+    } else {
+        Ok(run_batch_concurrent(&app, &bridge, &pending_approvals, &next_approval_id, items).await)
+    }
+}
+
+/// Fan every item out concurrently via [`execute_intent`] and collect results in input order.
+/// No item's failure affects any other; see [`ai_intent_batch`]'s doc comment.
+async fn run_batch_concurrent(
+    app: &AppHandle,
+    bridge: &Arc<RwLock<Bridge>>,
+    pending_approvals: &Arc<Mutex<std::collections::HashMap<u64, PendingApproval>>>,
+    next_approval_id: &Arc<AtomicU64>,
+    items: Vec<BatchIntentRequest>,
+) -> Vec<Value> {
+    let handles: Vec<_> = items
+        .into_iter()
+        .map(|item| {
+            let app = app.clone();
+            let bridge = Arc::clone(bridge);
+            let pending_approvals = Arc::clone(pending_approvals);
+            let next_approval_id = Arc::clone(next_approval_id);
+            async_runtime::spawn(async move {
+                execute_intent(
+                    &app,
+                    &bridge,
+                    &pending_approvals,
+                    &next_approval_id,
+                    item.intent,
+                    item.params,
+                    false,
+                )
+                .await
+            })
+        })
+        .collect();
+
+    let mut results = Vec::with_capacity(handles.len());
+    for handle in handles {
+        let envelope = match handle.await {
+            Ok(envelope) => envelope,
+            // A panicking task becomes a bridge_error entry rather than poisoning the batch.
+            Err(join_error) => bridge_error("unknown", format!("task panicked: {join_error}")),
+        };
+        results.push(envelope);
     }
+    results
+}
+
+/// Run every item one at a time, in order, stopping for good after the first fatal
+/// `BRIDGE_ERROR` or `BRIDGE_DISCONNECTED` envelope. Built on [`sequential_with_gate`] so the abort decision and the
+/// "did this index actually run" bookkeeping are unit-testable without a real [`Bridge`].
+async fn run_batch_sequential(
+    app: &AppHandle,
+    bridge: &Arc<RwLock<Bridge>>,
+    pending_approvals: &Arc<Mutex<std::collections::HashMap<u64, PendingApproval>>>,
+    next_approval_id: &Arc<AtomicU64>,
+    items: Vec<BatchIntentRequest>,
+) -> Vec<Value> {
+    let count = items.len();
+    let mut items = std::collections::VecDeque::from(items);
+
+    sequential_with_gate(count, |_index| {
+        let item = items
+            .pop_front()
+            .expect("sequential_with_gate calls indices 0..count exactly once, in order");
+        async move {
+            execute_intent(
+                app,
+                bridge,
+                pending_approvals,
+                next_approval_id,
+                item.intent,
+                item.params,
+                false,
+            )
+            .await
+        }
+    })
+    .await
 }
 
+/// Call `call(0)`, `call(1)`, ... in order, awaiting each before starting the next. Once an
+/// invocation returns a [`is_fatal_bridge_error`] envelope, every remaining index is filled
+/// with a `skipped` placeholder instead of being called at all — so, unlike a
+/// spawn-then-abort scheme, a "skipped" result here is never issued for an index whose `call`
+/// already ran. Pure aside from `call` itself, so it's directly unit-testable with fake
+/// closures.
+async fn sequential_with_gate<F, Fut>(count: usize, mut call: F) -> Vec<Value>
+where
+    F: FnMut(usize) -> Fut,
+    Fut: std::future::Future<Output = Value>,
+{
+    let mut aborted = false;
+    let mut results = Vec::with_capacity(count);
+
+    for i in 0..count {
+        if aborted {
+            results.push(bridge_error(
+                "unknown",
+                "skipped: a prior batch item returned a fatal bridge error".to_string(),
+            ));
+            continue;
+        }
+
+        let envelope = call(i).await;
+        if is_fatal_bridge_error(&envelope) {
+            aborted = true;
+        }
+        results.push(envelope);
+    }
+
+    results
+}
+
+/// Whether `envelope` is a fatal bridge-level failure — `BRIDGE_ERROR` (the bridge call
+/// itself failed) or `BRIDGE_DISCONNECTED` (there's no bridge session to call at all, so
+/// every remaining item would fail the same way) — as opposed to a declined-but-not-broken
+/// outcome like `UNKNOWN_INTENT`, `INVALID_PARAMS`, or `APPROVAL_REQUIRED`.
+fn is_fatal_bridge_error(envelope: &Value) -> bool {
+    matches!(
+        envelope.get("error").and_then(|error| error.get("code")).and_then(Value::as_str),
+        Some("BRIDGE_ERROR") | Some("BRIDGE_DISCONNECTED")
+    )
+}
+
+/// Storage-mode switches restart the backend against a different persistence layer, which
+/// is destructive enough to go through the same stash-and-emit approval handshake as
+/// [`GUARDED_INTENTS`], rather than applying immediately. The switch itself runs from
+/// [`respond_intent`]'s `Approved` arm once the frontend confirms.
 #[tauri::command]
 pub async fn backend_set_storage_mode(
+    app: AppHandle,
     state: State<'_, AppState>,
     mode: String,
 ) -> Result<BackendStorageModeResponse, String> {
-    let bridge = state.bridge.lock().await;
+    let id = state.next_approval_id.fetch_add(1, Ordering::SeqCst);
+
+    {
+        let mut pending = state.pending_approvals.lock().await;
+        pending.insert(
+            id,
+            PendingApproval {
+                intent: "set_storage_mode".to_string(),
+                action: PendingAction::StorageMode { mode: mode.clone() },
+            },
+        );
+    }
 
-    match bridge.set_storage_mode(&mode).await {
-        Ok(restarted) => Ok(BackendStorageModeResponse {
+    let _ = app.emit(
+        "approval-requested",
+        ApprovalRequestedPayload {
+            id,
+            tool_name: "backend_set_storage_mode".to_string(),
+            summary: format!("Switch storage mode to `{mode}`? This restarts the backend."),
+        },
+    );
+
+    Ok(BackendStorageModeResponse {
+        success: false,
+        mode,
+        restarted: false,
+        error: Some("storage mode switches require frontend confirmation; call respond_intent with this id".to_string()),
+        pending_approval_id: Some(id),
+    })
+}
+
+/// Response shared by the bridge lifecycle commands (`backend_disconnect`, `backend_reconnect`,
+/// `backend_status`)
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+pub struct BackendStatusResponse {
+    pub success: bool,
+    pub connected: bool,
+    pub mode: String,
+    pub error: Option<String>,
+}
+
+/// Cleanly stop the current bridge context, freeing its socket/child process without
+/// dropping `AppState` itself, so a subsequent `backend_reconnect` can re-establish it.
+#[tauri::command]
+pub async fn backend_disconnect(state: State<'_, AppState>) -> Result<BackendStatusResponse, String> {
+    let mut bridge = state.bridge.write().await;
+
+    match bridge.disconnect().await {
+        Ok(()) => Ok(BackendStatusResponse {
+            success: true,
+            connected: bridge.is_connected(),
+            mode: bridge.storage_mode_str().to_string(),
+            error: None,
+        }),
+        Err(e) => Ok(BackendStatusResponse {
+            success: false,
+            connected: bridge.is_connected(),
+            mode: bridge.storage_mode_str().to_string(),
+            error: Some(e.to_string()),
+        }),
+    }
+}
+
+/// Re-establish the bridge connection and re-apply the storage mode that was active before
+/// the disconnect (or after a connection was lost unexpectedly).
+#[tauri::command]
+pub async fn backend_reconnect(state: State<'_, AppState>) -> Result<BackendStatusResponse, String> {
+    let mut bridge = state.bridge.write().await;
+
+    match bridge.reconnect().await {
+        Ok(()) => Ok(BackendStatusResponse {
             success: true,
+            connected: bridge.is_connected(),
             mode: bridge.storage_mode_str().to_string(),
-            restarted,
             error: None,
         }),
-        Err(e) => Ok(BackendStorageModeResponse {
+        Err(e) => Ok(BackendStatusResponse {
             success: false,
-            mode,
-            restarted: false,
+            connected: bridge.is_connected(),
+            mode: bridge.storage_mode_str().to_string(),
             error: Some(e.to_string()),
         }),
     }
 }
+
+/// Report connection liveness, the currently active storage mode, and the last bridge error
+/// (if any), so the UI can decide whether to offer a reconnect action.
+#[tauri::command]
+pub async fn backend_status(state: State<'_, AppState>) -> Result<BackendStatusResponse, String> {
+    let bridge = state.bridge.read().await;
+
+    Ok(BackendStatusResponse {
+        success: true,
+        connected: bridge.is_connected(),
+        mode: bridge.storage_mode_str().to_string(),
+        error: bridge.last_error().map(|e| e.to_string()),
+    })
+}
+
+/// A single registered intent and its declared parameter schema, as returned by
+/// [`list_intents`] so the frontend can build forms dynamically.
+#[derive(Debug, serde::Serialize)]
+pub struct IntentDescriptor {
+    pub name: String,
+    pub params: Vec<IntentParamDescriptor>,
+}
+
+#[derive(Debug, serde::Serialize)]
+pub struct IntentParamDescriptor {
+    pub name: String,
+    pub required: bool,
+    pub kind: &'static str,
+}
+
+/// List every registered `tasks_<intent>` with its parameter schema.
+#[tauri::command]
+pub fn list_intents() -> Vec<IntentDescriptor> {
+    registry::REGISTRY
+        .iter()
+        .map(|schema| IntentDescriptor {
+            name: schema.name.to_string(),
+            params: schema
+                .params
+                .iter()
+                .map(|spec| IntentParamDescriptor {
+                    name: spec.name.to_string(),
+                    required: spec.required,
+                    kind: spec.kind.as_str(),
+                })
+                .collect(),
+        })
+        .collect()
+}
+
+/// Frontend state get/set response
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+pub struct FrontendStateResponse {
+    pub success: bool,
+    pub state: Option<Value>,
+    pub error: Option<String>,
+}
+
+/// Read the opaque, frontend-owned state blob for a namespace (open tabs, filter selections,
+/// last-used intent params, ...). Stored through the bridge so it lives next to whichever
+/// storage mode is currently active and survives app restarts.
+#[tauri::command]
+pub async fn get_frontend_state(
+    state: State<'_, AppState>,
+    namespace: String,
+) -> Result<FrontendStateResponse, String> {
+    let bridge = state.bridge.read().await;
+
+    match bridge.get_frontend_state(&namespace).await {
+        Ok(value) => Ok(FrontendStateResponse {
+            success: true,
+            state: value,
+            error: None,
+        }),
+        Err(e) => Ok(FrontendStateResponse {
+            success: false,
+            state: None,
+            error: Some(e.to_string()),
+        }),
+    }
+}
+
+/// Persist an opaque, frontend-owned state blob under a namespace so multiple UI surfaces
+/// (e.g. a dashboard vs. a settings panel) can keep their own durable session state.
+#[tauri::command]
+pub async fn set_frontend_state(
+    state: State<'_, AppState>,
+    namespace: String,
+    value: Value,
+) -> Result<FrontendStateResponse, String> {
+    let bridge = state.bridge.write().await;
+
+    match bridge.set_frontend_state(&namespace, value.clone()).await {
+        Ok(()) => Ok(FrontendStateResponse {
+            success: true,
+            state: Some(value),
+            error: None,
+        }),
+        Err(e) => Ok(FrontendStateResponse {
+            success: false,
+            state: None,
+            error: Some(e.to_string()),
+        }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::RefCell;
+
+    #[test]
+    fn is_fatal_bridge_error_fires_on_bridge_error_and_disconnected_codes() {
+        let fatal = [bridge_error("whatever", "boom".to_string()), bridge_disconnected_error("list")];
+        for envelope in fatal {
+            assert!(is_fatal_bridge_error(&envelope), "should be fatal: {envelope}");
+        }
+
+        let non_fatal = [
+            unknown_intent_error("lst", vec!["list".to_string()]),
+            invalid_params_error("get", "id", "missing required field `id`"),
+            approval_required("delete", 1),
+        ];
+        for envelope in non_fatal {
+            assert!(!is_fatal_bridge_error(&envelope), "should not be fatal: {envelope}");
+        }
+    }
+
+    #[tokio::test]
+    async fn sequential_with_gate_runs_every_index_when_nothing_fails() {
+        let calls = RefCell::new(Vec::new());
+        let results = sequential_with_gate(3, |i| {
+            calls.borrow_mut().push(i);
+            async move { json!({ "success": true }) }
+        })
+        .await;
+
+        assert_eq!(*calls.borrow(), vec![0, 1, 2]);
+        assert_eq!(results.len(), 3);
+    }
+
+    #[tokio::test]
+    async fn sequential_with_gate_stops_for_good_after_a_fatal_error() {
+        let calls = RefCell::new(Vec::new());
+        let results = sequential_with_gate(4, |i| {
+            calls.borrow_mut().push(i);
+            async move {
+                if i == 1 {
+                    bridge_error("whatever", "boom".to_string())
+                } else {
+                    json!({ "success": true })
+                }
+            }
+        })
+        .await;
+
+        // Indices 2 and 3 are never called once index 1 reports a fatal BRIDGE_ERROR.
+        assert_eq!(*calls.borrow(), vec![0, 1]);
+        assert_eq!(results.len(), 4);
+        assert!(is_fatal_bridge_error(&results[2]));
+        assert!(is_fatal_bridge_error(&results[3]));
+    }
+
+    #[tokio::test]
+    async fn sequential_with_gate_keeps_going_past_a_non_fatal_failure() {
+        let calls = RefCell::new(Vec::new());
+        let results = sequential_with_gate(3, |i| {
+            calls.borrow_mut().push(i);
+            async move { invalid_params_error("create", "title", "missing required field `title`") }
+        })
+        .await;
+
+        assert_eq!(*calls.borrow(), vec![0, 1, 2]);
+        assert_eq!(results.len(), 3);
+    }
+}
@@ -0,0 +1,224 @@
+//! Optional embedded HTTP server that re-exposes the `tasks_<intent>` surface `ai_intent`
+//! proxies, so external tools and scripts can drive the app without going through the
+//! webview's `invoke`. Only compiled when the `http-server` feature is enabled; the router
+//! is mounted as a Tauri async task during setup and shares the same bridge handle (and
+//! therefore the same storage mode) as the `ai_intent` command path.
+
+use std::fmt;
+use std::sync::Arc;
+
+use axum::body::Bytes;
+use axum::extract::{Path, State};
+use axum::http::{HeaderMap, StatusCode};
+use axum::routing::post;
+use axum::{Json, Router};
+use serde_json::{json, Value};
+use tokio::sync::RwLock;
+
+use crate::bridge::Bridge;
+use crate::commands::registry;
+use crate::commands::task::{
+    guarded_intent_forbidden, invalid_params_error, invoke_checked, is_guarded_intent, unknown_intent_error,
+};
+
+/// Configuration for the embedded intent HTTP server.
+#[derive(Clone)]
+pub struct HttpServerConfig {
+    /// Port to bind on loopback (`127.0.0.1`). Read from `APPLY_TASK_HTTP_PORT` at startup
+    /// (default 4317); not exposed on any non-loopback interface.
+    pub port: u16,
+    /// Bearer token clients must present in `Authorization: Bearer <token>`.
+    pub token: String,
+}
+
+// Manual `Debug` so the bearer token never ends up in a log line via a stray `{:?}`.
+impl fmt::Debug for HttpServerConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("HttpServerConfig")
+            .field("port", &self.port)
+            .field("token", &"***")
+            .finish()
+    }
+}
+
+#[derive(Clone)]
+struct HttpState {
+    bridge: Arc<RwLock<Bridge>>,
+    token: String,
+}
+
+/// Build the router and serve it on `127.0.0.1:{config.port}` until the returned future is
+/// dropped. Intended to be spawned via `tauri::async_runtime::spawn` during app setup so it
+/// shares the runtime (and the bridge lock) with the rest of the app.
+pub async fn serve(bridge: Arc<RwLock<Bridge>>, config: HttpServerConfig) -> std::io::Result<()> {
+    let state = HttpState { bridge, token: config.token };
+    let app = Router::new()
+        .route("/intent/{name}", post(handle_intent))
+        .with_state(state);
+
+    let listener = tokio::net::TcpListener::bind(("127.0.0.1", config.port)).await?;
+    axum::serve(listener, app).await
+}
+
+fn unauthorized() -> Value {
+    json!({
+        "success": false,
+        "intent": "",
+        "result": {},
+        "warnings": [],
+        "context": {},
+        "suggestions": [],
+        "meta": {},
+        "error": { "code": "UNAUTHORIZED", "message": "missing or invalid bearer token" },
+        "timestamp": ""
+    })
+}
+
+fn malformed_body_error(intent: &str, message: &str) -> Value {
+    json!({
+        "success": false,
+        "intent": intent,
+        "result": {},
+        "warnings": [],
+        "context": {},
+        "suggestions": [],
+        "meta": {},
+        "error": { "code": "MALFORMED_BODY", "message": message },
+        "timestamp": ""
+    })
+}
+
+/// Constant-time byte comparison so a timing side channel can't be used to guess the bearer
+/// token one byte at a time.
+fn constant_time_eq(a: &str, b: &str) -> bool {
+    let (a, b) = (a.as_bytes(), b.as_bytes());
+    if a.len() != b.len() {
+        return false;
+    }
+    a.iter().zip(b.iter()).fold(0u8, |diff, (x, y)| diff | (x ^ y)) == 0
+}
+
+fn is_authorized(headers: &HeaderMap, token: &str) -> bool {
+    headers
+        .get(axum::http::header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "))
+        .map(|presented| constant_time_eq(presented, token))
+        .unwrap_or(false)
+}
+
+async fn handle_intent(
+    State(state): State<HttpState>,
+    Path(name): Path<String>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> (StatusCode, Json<Value>) {
+    if !is_authorized(&headers, &state.token) {
+        return (StatusCode::UNAUTHORIZED, Json(unauthorized()));
+    }
+
+    let normalized_intent = name.trim().to_lowercase();
+    let tool_name = format!("tasks_{}", normalized_intent);
+
+    // An empty body means "no params"; anything else must be valid JSON — it's never
+    // silently dropped in favor of `{}`.
+    let params = if body.is_empty() {
+        json!({})
+    } else {
+        match serde_json::from_slice::<Value>(&body) {
+            Ok(value) => value,
+            Err(e) => {
+                return (
+                    StatusCode::BAD_REQUEST,
+                    Json(malformed_body_error(&normalized_intent, &e.to_string())),
+                );
+            }
+        }
+    };
+
+    let Some(schema) = registry::find(&normalized_intent) else {
+        return (
+            StatusCode::NOT_FOUND,
+            Json(unknown_intent_error(&normalized_intent, registry::suggestions(&normalized_intent))),
+        );
+    };
+
+    if let Err(err) = registry::validate(schema, &params) {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(invalid_params_error(&normalized_intent, &err.field, &err.message)),
+        );
+    }
+
+    // The HTTP surface has no frontend to show an approval dialog, so destructive intents
+    // are refused outright here rather than silently bypassing the approval handshake.
+    if is_guarded_intent(&normalized_intent) {
+        return (StatusCode::FORBIDDEN, Json(guarded_intent_forbidden(&normalized_intent)));
+    }
+
+    // Same rule `execute_intent` applies on the Tauri command side: the bridge's single MCP
+    // channel isn't proven safe for overlapping in-flight calls, so a mutating intent takes
+    // the exclusive writer lock (serializing against every other call, including concurrent
+    // HTTP requests) while a read-only one only takes the reader side.
+    let envelope = if schema.mutates {
+        let bridge = state.bridge.write().await;
+        invoke_checked(&bridge, &normalized_intent, &tool_name, params).await
+    } else {
+        let bridge = state.bridge.read().await;
+        invoke_checked(&bridge, &normalized_intent, &tool_name, params).await
+    };
+
+    (StatusCode::OK, Json(envelope))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::http::HeaderValue;
+
+    #[test]
+    fn constant_time_eq_accepts_matching_strings() {
+        assert!(constant_time_eq("s3cr3t", "s3cr3t"));
+    }
+
+    #[test]
+    fn constant_time_eq_rejects_a_mismatch() {
+        assert!(!constant_time_eq("s3cr3t", "wrong"));
+    }
+
+    #[test]
+    fn constant_time_eq_rejects_different_lengths() {
+        assert!(!constant_time_eq("short", "much-longer"));
+    }
+
+    fn headers_with_bearer(token: &str) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            axum::http::header::AUTHORIZATION,
+            HeaderValue::from_str(&format!("Bearer {token}")).unwrap(),
+        );
+        headers
+    }
+
+    #[test]
+    fn is_authorized_accepts_the_correct_bearer_token() {
+        assert!(is_authorized(&headers_with_bearer("s3cr3t"), "s3cr3t"));
+    }
+
+    #[test]
+    fn is_authorized_rejects_an_incorrect_bearer_token() {
+        assert!(!is_authorized(&headers_with_bearer("wrong"), "s3cr3t"));
+    }
+
+    #[test]
+    fn is_authorized_rejects_a_missing_header() {
+        assert!(!is_authorized(&HeaderMap::new(), "s3cr3t"));
+    }
+
+    #[test]
+    fn is_authorized_rejects_a_non_bearer_scheme() {
+        let mut headers = HeaderMap::new();
+        headers.insert(axum::http::header::AUTHORIZATION, HeaderValue::from_static("Basic s3cr3t"));
+        assert!(!is_authorized(&headers, "s3cr3t"));
+    }
+}
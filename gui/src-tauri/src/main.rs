@@ -0,0 +1,94 @@
+//! Tauri application entry point: wires the command surface and the shared MCP bridge
+//! handle into a single running app.
+#![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
+
+use std::collections::HashMap;
+use std::sync::atomic::AtomicU64;
+use std::sync::Arc;
+
+use tokio::sync::{Mutex, RwLock};
+
+mod bridge;
+mod commands;
+#[cfg(feature = "http-server")]
+mod http_server;
+
+use bridge::Bridge;
+use commands::task::PendingApproval;
+
+/// Shared state every Tauri command reaches through `State<'_, AppState>`.
+pub struct AppState {
+    pub bridge: Arc<RwLock<Bridge>>,
+    pub pending_approvals: Arc<Mutex<HashMap<u64, PendingApproval>>>,
+    pub next_approval_id: Arc<AtomicU64>,
+}
+
+impl AppState {
+    pub fn new(bridge: Bridge) -> Self {
+        Self {
+            bridge: Arc::new(RwLock::new(bridge)),
+            pending_approvals: Arc::new(Mutex::new(HashMap::new())),
+            next_approval_id: Arc::new(AtomicU64::new(1)),
+        }
+    }
+}
+
+fn main() {
+    let state = AppState::new(Bridge::new("task-mcp-server", "sqlite"));
+
+    // Cloned before `state` moves into `.manage(..)` so `.setup()` below and the spawned
+    // HTTP server (which shares the same bridge handle, and therefore the same storage
+    // mode, as the `ai_intent` command path) both still have a handle to it.
+    let startup_bridge = Arc::clone(&state.bridge);
+    #[cfg(feature = "http-server")]
+    let http_bridge = Arc::clone(&state.bridge);
+
+    tauri::Builder::default()
+        .manage(state)
+        .setup(move |_app| {
+            // Connect to the MCP backend before the app starts serving commands; every
+            // intent call otherwise returns `BRIDGE_DISCONNECTED` until something calls
+            // `backend_reconnect`. A connection failure here is logged, not fatal — the UI
+            // surfaces it via `backend_status` and the user can retry with `backend_reconnect`.
+            let connected = tauri::async_runtime::block_on(async { startup_bridge.write().await.reconnect().await });
+            if let Err(e) = connected {
+                eprintln!("bridge: failed to connect to the MCP backend on startup: {e}");
+            }
+
+            #[cfg(feature = "http-server")]
+            {
+                let token = std::env::var("APPLY_TASK_HTTP_TOKEN").unwrap_or_default();
+                if token.is_empty() {
+                    // An empty token would make `constant_time_eq` accept an empty
+                    // `Authorization: Bearer ` header, i.e. no auth at all — refuse to
+                    // start rather than silently exposing the intent surface.
+                    eprintln!("APPLY_TASK_HTTP_TOKEN is not set; the http-server feature requires a bearer token, refusing to start it");
+                } else {
+                    let config = http_server::HttpServerConfig {
+                        port: std::env::var("APPLY_TASK_HTTP_PORT").ok().and_then(|p| p.parse().ok()).unwrap_or(4317),
+                        token,
+                    };
+                    tauri::async_runtime::spawn(async move {
+                        if let Err(e) = http_server::serve(http_bridge, config).await {
+                            eprintln!("http-server: failed to serve the intent endpoint: {e}");
+                        }
+                    });
+                }
+            }
+            Ok(())
+        })
+        .invoke_handler(tauri::generate_handler![
+            commands::task::ai_intent,
+            commands::task::respond_intent,
+            commands::task::ai_intent_batch,
+            commands::task::backend_set_storage_mode,
+            commands::task::backend_disconnect,
+            commands::task::backend_reconnect,
+            commands::task::backend_status,
+            commands::task::list_intents,
+            commands::task::get_frontend_state,
+            commands::task::set_frontend_state,
+        ])
+        .run(tauri::generate_context!())
+        .expect("error while running tauri application");
+}